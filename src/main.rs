@@ -1,19 +1,99 @@
 use std::thread;
 use std::time::Duration;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use std::error::Error;
+use std::collections::{HashMap, VecDeque};
+use std::io::{Cursor, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
 use clap::Parser;
-use sysinfo::System;
+use regex::Regex;
+use sysinfo::{Components, Disks, System};
 use std::process::Command;
 use std::str;
-use reqwest::blocking::Client;
+use reqwest::blocking::{Body, Client};
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::flag;
+
+/// Points are flushed once this many are buffered...
+const INFLUX_BATCH_MAX_POINTS: usize = 512;
+/// ...or once this long has passed since the last successful flush, whichever comes first.
+const INFLUX_BATCH_MAX_AGE: Duration = Duration::from_secs(1);
+/// Upper bound on queued-but-unsent points; oldest points are dropped once exceeded.
+const INFLUX_QUEUE_CAPACITY: usize = 8192;
+/// How often the writer thread wakes up to check batch age / queued commands.
+const INFLUX_WRITER_TICK: Duration = Duration::from_millis(200);
+const INFLUX_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const INFLUX_MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// Connection must be established within this long.
+const INFLUX_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Upper bound on an entire request, regardless of throughput. This is the backstop for
+/// a server that accepts the connection and the request body but never sends a response:
+/// `StallGuardReader` below only watches bytes leaving through the request body, so a
+/// stall on the read side (waiting for response headers) is caught here, not there.
+const INFLUX_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// A write streaming below this many bytes/sec for longer than the grace period below
+/// is considered stalled and aborted, rather than left to hang until INFLUX_REQUEST_TIMEOUT.
+const INFLUX_MIN_THROUGHPUT_BYTES_PER_SEC: u64 = 1;
+const INFLUX_MIN_THROUGHPUT_GRACE: Duration = Duration::from_secs(5);
+/// `StallGuardReader` never hands back more than this many bytes per `read()` call, so
+/// even a batch that fits reqwest's internal buffer in one shot still crosses several
+/// `read()` calls — giving a server that stalls mid-upload a chance to be noticed instead
+/// of the whole body finishing before any real I/O happens.
+const INFLUX_STALL_GUARD_MAX_CHUNK_BYTES: usize = 256;
+
+/// The scheduler wakes up this often to check which per-metric intervals have elapsed.
+const SCHEDULER_BASE_TICK: Duration = Duration::from_millis(500);
 
 #[derive(Parser, Debug)]
 #[command(author,version,about, long_about=None)]
 struct Args {
-    /// Interval for querying in seconds
-    #[arg(short, long)]
-    interval: u64,
+    /// Sampling interval for CPU usage, in seconds
+    #[arg(long, default_value_t = 1)]
+    cpu_interval: u64,
+
+    /// Sampling interval for RAM usage, in seconds
+    #[arg(long, default_value_t = 1)]
+    mem_interval: u64,
+
+    /// Sampling interval for network traffic counters, in seconds
+    #[arg(long, default_value_t = 2)]
+    net_interval: u64,
+
+    /// Sampling interval for GPU usage/temperature/power, in seconds
+    #[arg(long, default_value_t = 1)]
+    gpu_interval: u64,
+
+    /// Sampling interval for the heaviest-process lookup, in seconds (this refreshes
+    /// all processes and is comparatively expensive, so it defaults to a slower cadence)
+    #[arg(long, default_value_t = 5)]
+    process_interval: u64,
+
+    /// Sampling interval for disk usage and I/O throughput, in seconds
+    #[arg(long, default_value_t = 1)]
+    disk_interval: u64,
+
+    /// Sampling interval for UDP/IP protocol error counters, in seconds (these are
+    /// cumulative counters, so a slower cadence is fine)
+    #[arg(long, default_value_t = 10)]
+    snmp_interval: u64,
+
+    /// Only report processes whose name matches this pattern. By default this is a
+    /// full regex, compiled once at startup; pass --process-simple for substring matching
+    #[arg(long)]
+    process_filter: Option<String>,
+
+    /// Treat --process-filter as a plain substring match instead of a regex
+    #[arg(long)]
+    process_simple: bool,
+
+    /// Sum CPU/memory usage across processes sharing a name into one point per name,
+    /// instead of one point per PID (e.g. all Chrome worker processes combined).
+    /// Only meaningful alongside --process-filter, since without a filter there's no
+    /// process_metrics output to group.
+    #[arg(long, requires = "process_filter")]
+    group_processes: bool,
 
     /// Optionally exclude GPU
     #[arg(long)]
@@ -43,70 +123,539 @@ struct NetworkTraffic {
     timestamp: u64,
 }
 
+/// Tracks per-block-device sector counters so disk read/write throughput can be
+/// computed as a delta, the same way `NetworkTraffic` does for network counters.
+struct DiskTraffic {
+    last_sectors: HashMap<String, (u64, u64)>,
+    timestamp: u64,
+}
+
+/// A process-name matcher compiled once at startup from `--process-filter`, so matching
+/// never has to parse a pattern on the hot path.
+enum ProcessFilter {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl ProcessFilter {
+    /// Compiles `pattern` as a plain substring match if `simple` is set, or as a full
+    /// regex otherwise.
+    fn compile(pattern: &str, simple: bool) -> Result<Self, Box<dyn Error>> {
+        if simple {
+            Ok(ProcessFilter::Substring(pattern.to_string()))
+        } else {
+            Ok(ProcessFilter::Regex(Regex::new(pattern)?))
+        }
+    }
+
+    fn matches(&self, process_name: &str) -> bool {
+        match self {
+            ProcessFilter::Substring(needle) => process_name.contains(needle.as_str()),
+            ProcessFilter::Regex(re) => re.is_match(process_name),
+        }
+    }
+}
+
+/// UDP counters to pull out of /proc/net/snmp; see `get_network_protocol_metrics`.
+const SNMP_UDP_FIELDS: [&str; 7] = [
+    "InDatagrams",
+    "OutDatagrams",
+    "NoPorts",
+    "InErrors",
+    "RcvbufErrors",
+    "SndbufErrors",
+    "InCsumErrors",
+];
+
+/// /proc/net/snmp counters are cumulative since boot; this remembers the last value seen
+/// for each field so they can also be reported as per-interval deltas.
+struct SnmpTraffic {
+    last_values: HashMap<String, u64>,
+    timestamp: u64,
+}
+
+impl SnmpTraffic {
+    fn new() -> Self {
+        Self {
+            last_values: HashMap::new(),
+            timestamp: 0,
+        }
+    }
+
+    /// Returns the change in `value` for `key` since the last call (0 on the first
+    /// observation, since there's nothing yet to diff against), and remembers `value`.
+    fn delta(&mut self, key: &str, value: u64) -> i64 {
+        let delta: i64 = match self.last_values.get(key) {
+            Some(&last) if self.timestamp > 0 => value as i64 - last as i64,
+            _ => 0,
+        };
+        self.last_values.insert(key.to_string(), value);
+        delta
+    }
+}
+
+/// Parses /proc/net/snmp into protocol name -> field name -> value. The file pairs a
+/// header line (`Udp: InDatagrams NoPorts ...`) with a values line (`Udp: 1234 5 ...`);
+/// tokens after the protocol prefix are zipped together to build each field map.
+fn parse_proc_net_snmp(contents: &str) -> HashMap<String, HashMap<String, u64>> {
+    let mut protocols: HashMap<String, HashMap<String, u64>> = HashMap::new();
+    let lines: Vec<&str> = contents.lines().collect();
+
+    let mut i: usize = 0;
+    while i + 1 < lines.len() {
+        let header_fields: Vec<&str> = lines[i].split_whitespace().collect();
+        let value_fields: Vec<&str> = lines[i + 1].split_whitespace().collect();
+
+        match (header_fields.first(), value_fields.first()) {
+            (Some(header_proto), Some(value_proto)) if header_proto == value_proto => {
+                let mut fields: HashMap<String, u64> = HashMap::new();
+                for (name, value) in header_fields.iter().skip(1).zip(value_fields.iter().skip(1)) {
+                    if let Ok(value) = value.parse::<u64>() {
+                        fields.insert(name.to_string(), value);
+                    }
+                }
+
+                protocols.insert(header_proto.trim_end_matches(':').to_string(), fields);
+                i += 2;
+            }
+            _ => i += 1,
+        }
+    }
+
+    protocols
+}
+
+enum WriterCommand {
+    Point(String),
+    Flush(Sender<()>),
+}
+
+/// Wraps a request body so reqwest's blocking client aborts the write if upload
+/// throughput stays below `min_bytes_per_sec` for longer than `grace_period`, instead of
+/// hanging until `INFLUX_REQUEST_TIMEOUT`. The clock starts on the first byte read, so
+/// idle time before the request starts isn't penalized. Reads are chunked (see
+/// `INFLUX_STALL_GUARD_MAX_CHUNK_BYTES`) so a mid-upload stall is visible as a growing gap
+/// between calls, rather than the whole body finishing before any real I/O happens.
+struct StallGuardReader {
+    inner: Cursor<Vec<u8>>,
+    min_bytes_per_sec: u64,
+    grace_period: Duration,
+    window_start: Option<Instant>,
+    window_bytes: u64,
+    last_adequate_throughput: Option<Instant>,
+}
+
+impl StallGuardReader {
+    fn new(body: Vec<u8>, min_bytes_per_sec: u64, grace_period: Duration) -> Self {
+        Self {
+            inner: Cursor::new(body),
+            min_bytes_per_sec,
+            grace_period,
+            window_start: None,
+            window_bytes: 0,
+            last_adequate_throughput: None,
+        }
+    }
+}
+
+impl Read for StallGuardReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let chunk_len: usize = buf.len().min(INFLUX_STALL_GUARD_MAX_CHUNK_BYTES);
+        let n: usize = self.inner.read(&mut buf[..chunk_len])?;
+        if n == 0 {
+            return Ok(0); // EOF: nothing left to stall on.
+        }
+
+        let now: Instant = Instant::now();
+        let window_start: Instant = *self.window_start.get_or_insert(now);
+        self.last_adequate_throughput.get_or_insert(now);
+
+        self.window_bytes += n as u64;
+
+        let window_elapsed: Duration = now.duration_since(window_start);
+        if window_elapsed >= Duration::from_secs(1) {
+            let throughput: f64 = self.window_bytes as f64 / window_elapsed.as_secs_f64();
+            if throughput >= self.min_bytes_per_sec as f64 {
+                self.last_adequate_throughput = Some(now);
+            }
+            self.window_start = Some(now);
+            self.window_bytes = 0;
+        }
+
+        let last_adequate_throughput: Instant = self.last_adequate_throughput.expect("set above");
+        if now.duration_since(last_adequate_throughput) >= self.grace_period {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "InfluxDB write stalled below minimum throughput",
+            ));
+        }
+
+        Ok(n)
+    }
+}
+
+/// Buffers InfluxDB line-protocol points on a background thread so the sampling loop
+/// never blocks on HTTP. Points are batched by count/age, POSTed together, and retried
+/// with exponential backoff on failure instead of being dropped.
+struct InfluxWriter {
+    sender: Option<Sender<WriterCommand>>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl InfluxWriter {
+    /// Spawns the writer thread and returns a handle for pushing points onto it.
+    fn new(client: Client, url: String, org: String, bucket: String, token: String) -> Self {
+        let (sender, receiver) = mpsc::channel::<WriterCommand>();
+        let write_url: String = format!("{}/api/v2/write?org={}&bucket={}", url, org, bucket);
+
+        let handle: thread::JoinHandle<()> = thread::spawn(move || {
+            Self::run(receiver, client, write_url, token);
+        });
+
+        Self {
+            sender: Some(sender),
+            handle: Some(handle),
+        }
+    }
+
+    /// Queues a line-protocol point for the writer thread. Never blocks on HTTP.
+    fn push(&self, point: String) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(WriterCommand::Point(point));
+        }
+    }
+
+    /// Blocks until the writer thread has attempted to send everything queued so far.
+    fn flush(&self) {
+        if let Some(sender) = &self.sender {
+            let (ack_tx, ack_rx) = mpsc::channel();
+            if sender.send(WriterCommand::Flush(ack_tx)).is_ok() {
+                let _ = ack_rx.recv();
+            }
+        }
+    }
+
+    fn run(receiver: Receiver<WriterCommand>, client: Client, write_url: String, token: String) {
+        let mut buffer: VecDeque<String> = VecDeque::new();
+        let mut last_flush: Instant = Instant::now();
+        let mut backoff: Duration = INFLUX_INITIAL_BACKOFF;
+        let mut retry_at: Option<Instant> = None;
+
+        loop {
+            match receiver.recv_timeout(INFLUX_WRITER_TICK) {
+                Ok(WriterCommand::Point(point)) => {
+                    if buffer.len() >= INFLUX_QUEUE_CAPACITY {
+                        buffer.pop_front(); // Drop the oldest point to make room.
+                    }
+                    buffer.push_back(point);
+                }
+                Ok(WriterCommand::Flush(ack)) => {
+                    Self::send_batch(&client, &write_url, &token, &mut buffer, &mut backoff, &mut retry_at, true);
+                    last_flush = Instant::now();
+                    let _ = ack.send(());
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    // Sender (and InfluxWriter) was dropped: flush what's left and exit.
+                    Self::send_batch(&client, &write_url, &token, &mut buffer, &mut backoff, &mut retry_at, true);
+                    return;
+                }
+            }
+
+            let batch_is_due: bool = buffer.len() >= INFLUX_BATCH_MAX_POINTS || last_flush.elapsed() >= INFLUX_BATCH_MAX_AGE;
+            if batch_is_due
+                && !buffer.is_empty()
+                && Self::send_batch(&client, &write_url, &token, &mut buffer, &mut backoff, &mut retry_at, false)
+            {
+                last_flush = Instant::now();
+            }
+        }
+    }
+
+    /// Attempts to POST the whole buffer as one batch. On success, clears the buffer and
+    /// resets backoff. On failure, leaves the buffer queued and schedules the next retry.
+    /// A pending retry delay is honored unless `force` is set, in which case the send is
+    /// attempted immediately.
+    fn send_batch(
+        client: &Client,
+        write_url: &str,
+        token: &str,
+        buffer: &mut VecDeque<String>,
+        backoff: &mut Duration,
+        retry_at: &mut Option<Instant>,
+        force: bool,
+    ) -> bool {
+        if buffer.is_empty() {
+            return false;
+        }
+        if !force {
+            if let Some(scheduled) = *retry_at {
+                if Instant::now() < scheduled {
+                    return false;
+                }
+            }
+        }
+
+        let body_bytes: Vec<u8> = buffer.iter().cloned().collect::<Vec<_>>().join("\n").into_bytes();
+        let body_len: u64 = body_bytes.len() as u64;
+        let stall_guard: StallGuardReader = StallGuardReader::new(
+            body_bytes,
+            INFLUX_MIN_THROUGHPUT_BYTES_PER_SEC,
+            INFLUX_MIN_THROUGHPUT_GRACE,
+        );
+
+        let response: Result<reqwest::blocking::Response, reqwest::Error> = client
+            .post(write_url)
+            .header("Authorization", format!("Token {}", token))
+            .body(Body::sized(stall_guard, body_len))
+            .send();
+
+        match response {
+            Ok(resp) if resp.status().is_success() => {
+                println!("Flushed {} point(s) to InfluxDB, status: {}", buffer.len(), resp.status());
+                buffer.clear();
+                *backoff = INFLUX_INITIAL_BACKOFF;
+                *retry_at = None;
+                true
+            }
+            Ok(resp) => {
+                eprintln!("InfluxDB rejected batch, status: {}, retrying in {:?}", resp.status(), backoff);
+                *retry_at = Some(Instant::now() + *backoff);
+                *backoff = (*backoff * 2).min(INFLUX_MAX_BACKOFF);
+                false
+            }
+            Err(e) => {
+                eprintln!("Failed to send batch to InfluxDB: {}, retrying in {:?}", e, backoff);
+                *retry_at = Some(Instant::now() + *backoff);
+                *backoff = (*backoff * 2).min(INFLUX_MAX_BACKOFF);
+                false
+            }
+        }
+    }
+}
+
+impl Drop for InfluxWriter {
+    /// Disconnects the channel so the writer thread drains its buffer and exits,
+    /// then waits for it to finish so queued points get one final send attempt on
+    /// shutdown. Points still unsent if that attempt fails are dropped.
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     // Command line arguments and parsing
     let args: Args = Args::parse();
-    let interval: u64 = args.interval;
     let exclude_gpu: bool = args.exclude_gpu;
-    
+
+    let cpu_interval: Duration = Duration::from_secs(args.cpu_interval);
+    let mem_interval: Duration = Duration::from_secs(args.mem_interval);
+    let net_interval: Duration = Duration::from_secs(args.net_interval);
+    let gpu_interval: Duration = Duration::from_secs(args.gpu_interval);
+    let process_interval: Duration = Duration::from_secs(args.process_interval);
+    let disk_interval: Duration = Duration::from_secs(args.disk_interval);
+    let snmp_interval: Duration = Duration::from_secs(args.snmp_interval);
+
+    let process_filter: Option<ProcessFilter> = match &args.process_filter {
+        Some(pattern) => Some(ProcessFilter::compile(pattern, args.process_simple)?),
+        None => None,
+    };
+    let group_processes: bool = args.group_processes;
+
     // Initialize system info
     let mut sys: System = System::new_all();
     let mut net_traffic: NetworkTraffic = NetworkTraffic::new();
+    let mut disks: Disks = Disks::new_with_refreshed_list();
+    let mut disk_traffic: DiskTraffic = DiskTraffic::new();
+    let mut components: Components = Components::new_with_refreshed_list();
+    let mut snmp_traffic: SnmpTraffic = SnmpTraffic::new();
+
+    let client: Client = Client::builder()
+        .connect_timeout(INFLUX_CONNECT_TIMEOUT)
+        .timeout(INFLUX_REQUEST_TIMEOUT)
+        .build()?;
+    let influx_writer: InfluxWriter = InfluxWriter::new(
+        client,
+        args.influxdb_url.clone(),
+        args.influxdb_org.clone(),
+        args.influxdb_bucket.clone(),
+        args.influxdb_token.clone(),
+    );
+
+    // On SIGINT/SIGTERM, break the sampling loop below instead of exiting the process
+    // outright, so `influx_writer` runs its `Drop` impl and drains any buffered points
+    // before `main` returns. `signal_hook::flag::register` catches both signals directly
+    // (unlike `ctrlc`, which only catches SIGTERM/SIGHUP behind its `termination` feature),
+    // so `systemd stop`/`docker stop`/k8s's SIGTERM still drain the queue instead of
+    // killing the process outright.
+    let shutdown_requested: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+    flag::register(SIGINT, Arc::clone(&shutdown_requested))?;
+    flag::register(SIGTERM, Arc::clone(&shutdown_requested))?;
+
+    // Cached latest values; each is only refreshed once its own interval has elapsed.
+    let mut cpu_usage: f32 = 0.0;
+    let mut ram_usage: f32 = 0.0;
+    let mut heaviest_process_name: String = String::new();
+    let mut download_rate: f64 = 0.0;
+    let mut upload_rate: f64 = 0.0;
+    let mut gpu_usage: f32 = -1.0;
+    let mut gpu_temp: f32 = -1.0;
+    let mut gpu_power: f32 = -1.0;
+
+    // Sampling each metric on the very first tick establishes a baseline.
+    let mut next_cpu: Instant = Instant::now();
+    let mut next_mem: Instant = Instant::now();
+    let mut next_net: Instant = Instant::now();
+    let mut next_gpu: Instant = Instant::now();
+    let mut next_process: Instant = Instant::now();
+    let mut next_disk: Instant = Instant::now();
+    let mut next_snmp: Instant = Instant::now();
 
-    let client: Client = Client::new();
-    
     loop {
-        // Get basic parameters
-        let now: SystemTime = SystemTime::now();
-        let timestamp: u128 = now.duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_nanos(); 
-
-        let cpu_usage: f32 = get_cpu_usage(&mut sys);
-        let ram_usage: f32 = get_ram_usage(&mut sys);
-        let heaviest_process_name: String = get_heaviest_process(&mut sys);
-
-        // Get network traffic data
-        let (download_rate, upload_rate) = match net_traffic.update() {
-            Ok((download_rate, upload_rate)) => (download_rate, upload_rate),
-            Err(e) => {
-                eprintln!("Failed to get network traffic: {}", e);
-                (-1.0, -1.0) // Use -1 as values for download/upload if net_traffic.update() fails
+        if shutdown_requested.load(Ordering::SeqCst) {
+            println!("Shutdown requested, flushing buffered points before exiting...");
+            influx_writer.flush();
+            break;
+        }
+
+        let tick: Instant = Instant::now();
+        let mut any_refreshed: bool = false;
+
+        if tick >= next_cpu {
+            cpu_usage = get_cpu_usage(&mut sys);
+            next_cpu = tick + cpu_interval;
+            any_refreshed = true;
+
+            // Per-core usage and sensor temperatures change on the same timescale as
+            // the global CPU average, so they're sampled and emitted alongside it.
+            let breakdown_timestamp: u128 = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_nanos();
+
+            for line in get_cpu_core_metrics(&mut sys, breakdown_timestamp) {
+                influx_writer.push(line);
             }
-        };
-        
-        // Get GPU usage data 
-        let (gpu_usage, gpu_temp, gpu_power) = get_gpu_info(exclude_gpu);
-        
-
-        // Prepare data for sending to InfluxDB
-        let data: String = format!(
-            "system_metrics,host=localhost cpu_usage={},ram_usage={},heaviest_process=\"{}\",gpu_usage={},gpu_temp={},gpu_power={},download_rate={},upload_rate={} {}",
-            cpu_usage,
-            ram_usage,
-            heaviest_process_name,
-            gpu_usage,
-            gpu_temp,
-            gpu_power,
-            download_rate,
-            upload_rate,
-            timestamp
-        );
-        
-        // Prepare Client and post response
-        
-        let response: Result<reqwest::blocking::Response, reqwest::Error> = client.post(&format!("{}/api/v2/write?org={}&bucket={}", args.influxdb_url, args.influxdb_org, args.influxdb_bucket))
-            .header("Authorization", format!("Token {}", args.influxdb_token))
-            .body(data)
-            .send();
-        
-        match response {
-            Ok(resp) => println!("Data sent to InfluxDB, status: {}", resp.status()),
-            Err(e) => eprintln!("Failed to send data to InfluxDB: {}", e),
+            for line in get_temperature_metrics(&mut components, breakdown_timestamp) {
+                influx_writer.push(line);
+            }
+        }
+
+        if tick >= next_mem {
+            ram_usage = get_ram_usage(&mut sys);
+            next_mem = tick + mem_interval;
+            any_refreshed = true;
+        }
+
+        if tick >= next_process {
+            heaviest_process_name = get_heaviest_process(&mut sys);
+            next_process = tick + process_interval;
+            any_refreshed = true;
+
+            if let Some(filter) = &process_filter {
+                let filter_timestamp: u128 = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Time went backwards")
+                    .as_nanos();
+
+                for line in get_filtered_process_metrics(&mut sys, filter, group_processes, filter_timestamp) {
+                    influx_writer.push(line);
+                }
+            }
+        }
+
+        if tick >= next_net {
+            match net_traffic.update() {
+                Ok((d, u)) => {
+                    download_rate = d;
+                    upload_rate = u;
+                }
+                Err(e) => {
+                    eprintln!("Failed to get network traffic: {}", e);
+                    download_rate = -1.0; // Use -1 as values for download/upload if net_traffic.update() fails
+                    upload_rate = -1.0;
+                }
+            }
+            next_net = tick + net_interval;
+            any_refreshed = true;
+        }
+
+        if tick >= next_gpu {
+            let (gu, gt, gp) = get_gpu_info(exclude_gpu);
+            gpu_usage = gu;
+            gpu_temp = gt;
+            gpu_power = gp;
+            next_gpu = tick + gpu_interval;
+            any_refreshed = true;
+        }
+
+        // Only emit a point when at least one metric was re-sampled this tick.
+        if any_refreshed {
+            let timestamp: u128 = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_nanos();
+
+            // Prepare data for sending to InfluxDB
+            let data: String = format!(
+                "system_metrics,host=localhost cpu_usage={},ram_usage={},heaviest_process=\"{}\",gpu_usage={},gpu_temp={},gpu_power={},download_rate={},upload_rate={} {}",
+                cpu_usage,
+                ram_usage,
+                heaviest_process_name,
+                gpu_usage,
+                gpu_temp,
+                gpu_power,
+                download_rate,
+                upload_rate,
+                timestamp
+            );
+
+            // Hand the point off to the background writer; this never blocks on HTTP.
+            influx_writer.push(data);
         }
-        
-        thread::sleep(Duration::from_secs(interval));
+
+        // Disk usage/throughput is its own measurement (one point per mounted filesystem),
+        // so it's scheduled and emitted independently of the combined system_metrics point.
+        if tick >= next_disk {
+            let timestamp: u128 = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_nanos();
+
+            for line in get_disk_info(&mut disks, &mut disk_traffic, timestamp) {
+                influx_writer.push(line);
+            }
+
+            next_disk = tick + disk_interval;
+        }
+
+        // UDP/IP counters are cumulative and change slowly, so they're sampled on
+        // their own (typically much longer) interval.
+        #[cfg(target_os = "linux")]
+        if tick >= next_snmp {
+            let timestamp: u128 = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_nanos();
+
+            match get_network_protocol_metrics(&mut snmp_traffic, timestamp) {
+                Ok(line) => influx_writer.push(line),
+                Err(e) => eprintln!("Failed to get network protocol stats: {}", e),
+            }
+
+            next_snmp = tick + snmp_interval;
+        }
+
+        thread::sleep(SCHEDULER_BASE_TICK);
     }
+
+    // `influx_writer` drops here, which disconnects the channel and joins the writer
+    // thread so the final batch gets a send attempt before the process exits.
+    Ok(())
 }
 
 impl NetworkTraffic {
@@ -121,6 +670,71 @@ impl NetworkTraffic {
     /// Updates the NetworkTraffic instance with the current network traffic data.
     /// Returns the download and upload rates in bytes/sec.
     fn update(&mut self) -> Result<(f64, f64), Box<dyn std::error::Error>> {
+        let (bytes_received, bytes_sent) = Self::read_byte_counters()?;
+
+        let now: u64 = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        // Saturating: the summed non-loopback interface set can shrink between samples
+        // (docker/veth/VPN interfaces coming and going), which would otherwise make the
+        // total look like it went backwards and underflow/wrap into a bogus spike.
+        let download_rate: f64 = if self.timestamp > 0 {
+            bytes_received.saturating_sub(self.bytes_received) as f64 / (now - self.timestamp) as f64
+        } else {
+            0.0
+        };
+
+        let upload_rate: f64 = if self.timestamp > 0 {
+            bytes_sent.saturating_sub(self.bytes_sent) as f64 / (now - self.timestamp) as f64
+        } else {
+            0.0
+        };
+
+        self.bytes_received = bytes_received;
+        self.bytes_sent = bytes_sent;
+        self.timestamp = now;
+
+        Ok((download_rate, upload_rate))
+    }
+
+    /// Reads cumulative received/sent byte counters for all non-loopback interfaces.
+    /// Uses `/proc/net/dev` on Linux and falls back to `netstat -e` on Windows.
+    #[cfg(target_os = "linux")]
+    fn read_byte_counters() -> Result<(u64, u64), Box<dyn std::error::Error>> {
+        let contents: String = std::fs::read_to_string("/proc/net/dev")?;
+
+        let mut bytes_received: u64 = 0;
+        let mut bytes_sent: u64 = 0;
+
+        // First two lines are headers, the rest are "iface: rx_bytes rx_packets ... tx_bytes tx_packets ..."
+        for line in contents.lines().skip(2) {
+            let mut split = line.splitn(2, ':');
+            let iface: &str = match split.next() {
+                Some(iface) => iface.trim(),
+                None => continue,
+            };
+            let rest: &str = match split.next() {
+                Some(rest) => rest,
+                None => continue,
+            };
+
+            if iface == "lo" {
+                continue;
+            }
+
+            let fields: Vec<&str> = rest.split_whitespace().collect();
+            if fields.len() < 9 {
+                continue;
+            }
+
+            bytes_received += fields[0].parse::<u64>().unwrap_or(0);
+            bytes_sent += fields[8].parse::<u64>().unwrap_or(0);
+        }
+
+        Ok((bytes_received, bytes_sent))
+    }
+
+    #[cfg(target_os = "windows")]
+    fn read_byte_counters() -> Result<(u64, u64), Box<dyn std::error::Error>> {
         let output: std::process::Output = Command::new("cmd")
             .args(&["/C", "netstat", "-e"])
             .output()?;
@@ -140,26 +754,108 @@ impl NetworkTraffic {
         let bytes_received: u64 = parts[1].parse()?;
         let bytes_sent: u64 = parts[2].parse()?;
 
-        let now: u64 = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        Ok((bytes_received, bytes_sent))
+    }
 
-        let download_rate: f64 = if self.timestamp > 0 {
-            (bytes_received - self.bytes_received) as f64 / (now - self.timestamp) as f64
-        } else {
-            0.0
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    fn read_byte_counters() -> Result<(u64, u64), Box<dyn std::error::Error>> {
+        Err("Network traffic collection is only supported on Linux and Windows".into())
+    }
+}
+
+impl DiskTraffic {
+    /// Creates a new DiskTraffic instance with no prior history.
+    fn new() -> Self {
+        Self {
+            last_sectors: HashMap::new(),
+            timestamp: 0,
+        }
+    }
+
+    /// Returns read/write bytes/sec for `device` since the last call, or `(0.0, 0.0)`
+    /// if there's no prior reading yet to diff against.
+    #[cfg(target_os = "linux")]
+    fn throughput_for(&mut self, device: &str, now: u64) -> (f64, f64) {
+        let (sectors_read, sectors_written) = match Self::read_sectors(device) {
+            Ok(sectors) => sectors,
+            Err(_) => return (-1.0, -1.0),
         };
 
-        let upload_rate: f64 = if self.timestamp > 0 {
-            (bytes_sent - self.bytes_sent) as f64 / (now - self.timestamp) as f64
-        } else {
-            0.0
+        let elapsed: u64 = now.saturating_sub(self.timestamp);
+        let rates: (f64, f64) = match self.last_sectors.get(device) {
+            Some(&(last_read, last_written)) if self.timestamp > 0 && elapsed > 0 => (
+                (sectors_read.saturating_sub(last_read) * 512) as f64 / elapsed as f64,
+                (sectors_written.saturating_sub(last_written) * 512) as f64 / elapsed as f64,
+            ),
+            _ => (0.0, 0.0),
         };
 
-        self.bytes_received = bytes_received;
-        self.bytes_sent = bytes_sent;
-        self.timestamp = now;
+        self.last_sectors.insert(device.to_string(), (sectors_read, sectors_written));
+        rates
+    }
 
-        Ok((download_rate, upload_rate))
+    #[cfg(not(target_os = "linux"))]
+    fn throughput_for(&mut self, _device: &str, _now: u64) -> (f64, f64) {
+        (-1.0, -1.0)
+    }
+
+    /// Reads sectors read/written for `device` from `/sys/block/<device>/stat`.
+    /// Fields 2 and 6 (0-indexed) are sectors read and sectors written; multiply by the
+    /// 512-byte sector size to get bytes.
+    #[cfg(target_os = "linux")]
+    fn read_sectors(device: &str) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+        let contents: String = std::fs::read_to_string(format!("/sys/block/{}/stat", device))?;
+        let fields: Vec<&str> = contents.split_whitespace().collect();
+        if fields.len() < 7 {
+            return Err(format!("Unexpected /sys/block/{}/stat format", device).into());
+        }
+
+        let sectors_read: u64 = fields[2].parse()?;
+        let sectors_written: u64 = fields[6].parse()?;
+
+        Ok((sectors_read, sectors_written))
+    }
+}
+
+/// Escapes a value for use as an InfluxDB line-protocol tag value: spaces, commas, and
+/// equals signs must be backslash-escaped, or they're parsed as tag/field separators
+/// instead of literal characters (e.g. a mount point like `/mnt/My Passport`).
+fn escape_tag_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+        .replace(' ', "\\ ")
+}
+
+/// Resolves the backing block device for `disk_name` (e.g. `/dev/sda1`, `/dev/dm-0`)
+/// to the whole-disk name keyed under `/sys/block/<dev>/stat` (e.g. `sda`). Device-mapper
+/// and `/dev/root` are canonicalized through `/dev` first, then the whole-disk/partition
+/// distinction is read from sysfs itself rather than guessed from the name, since LVM,
+/// NVMe, eMMC, and device-mapper devices don't reliably follow a `<base><digit>` pattern.
+fn block_device_name(disk_name: &str) -> String {
+    let name: &str = disk_name.trim_start_matches("/dev/");
+
+    let resolved: String = if name.starts_with("mapper/") || name == "root" {
+        std::fs::canonicalize(format!("/dev/{}", name))
+            .ok()
+            .and_then(|path| path.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| name.to_string())
+    } else {
+        name.to_string()
+    };
+
+    let sys_class_path: std::path::PathBuf = std::path::PathBuf::from("/sys/class/block").join(&resolved);
+    if sys_class_path.join("partition").is_file() {
+        if let Some(whole_disk) = std::fs::canonicalize(&sys_class_path)
+            .ok()
+            .and_then(|path| path.parent().and_then(|parent| parent.file_name()).map(|n| n.to_string_lossy().into_owned()))
+        {
+            return whole_disk;
+        }
     }
+
+    resolved
 }
 
 
@@ -169,18 +865,92 @@ fn get_cpu_usage(sys: &mut System) -> f32 {
     sys.global_cpu_info().cpu_usage()
 }
 
+/// Builds one `cpu_core` line-protocol point per logical core, tagged by core index,
+/// to surface per-core imbalance that the global CPU average hides. Assumes `sys` has
+/// already had its CPU stats refreshed this tick (by `get_cpu_usage`, which it's always
+/// called alongside), since `refresh_cpu` shouldn't run twice per tick.
+fn get_cpu_core_metrics(sys: &mut System, timestamp: u128) -> Vec<String> {
+    sys.cpus()
+        .iter()
+        .enumerate()
+        .map(|(core, cpu)| format!("cpu_core,core={} usage={} {}", core, cpu.cpu_usage(), timestamp))
+        .collect()
+}
+
+/// Builds one `temperature` line-protocol point per hardware sensor (CPU, motherboard,
+/// NVMe, ...) reported by sysinfo's `Components` API, tagged by sensor label.
+fn get_temperature_metrics(components: &mut Components, timestamp: u128) -> Vec<String> {
+    components.refresh_list();
+    components.refresh();
+
+    components
+        .list()
+        .iter()
+        .map(|component| {
+            format!(
+                "temperature,sensor={} celsius={} {}",
+                escape_tag_value(component.label()),
+                component.temperature(),
+                timestamp
+            )
+        })
+        .collect()
+}
+
+/// Builds one `network_protocol_stats` line-protocol point from /proc/net/snmp, with
+/// the requested UDP counters plus whatever IP-level counters the kernel reports.
+/// Each counter is emitted both as its raw cumulative value and as a `_delta` field
+/// (the change since the last call) so dashboards can alert on rising error rates.
+#[cfg(target_os = "linux")]
+fn get_network_protocol_metrics(snmp_traffic: &mut SnmpTraffic, timestamp: u128) -> Result<String, Box<dyn Error>> {
+    let contents: String = std::fs::read_to_string("/proc/net/snmp")?;
+    let protocols: HashMap<String, HashMap<String, u64>> = parse_proc_net_snmp(&contents);
+
+    let now: u64 = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let mut fields: Vec<String> = Vec::new();
+
+    if let Some(udp) = protocols.get("Udp") {
+        for &name in SNMP_UDP_FIELDS.iter() {
+            if let Some(&value) = udp.get(name) {
+                let key: String = format!("udp_{}", name.to_lowercase());
+                let delta: i64 = snmp_traffic.delta(&key, value);
+                fields.push(format!("{}={},{}_delta={}", key, value, key, delta));
+            }
+        }
+    }
+
+    if let Some(ip) = protocols.get("Ip") {
+        for (name, &value) in ip.iter() {
+            let key: String = format!("ip_{}", name.to_lowercase());
+            let delta: i64 = snmp_traffic.delta(&key, value);
+            fields.push(format!("{}={},{}_delta={}", key, value, key, delta));
+        }
+    }
+
+    snmp_traffic.timestamp = now;
+
+    if fields.is_empty() {
+        return Err("No UDP/IP counters found in /proc/net/snmp".into());
+    }
+
+    Ok(format!("network_protocol_stats,host=localhost {} {}", fields.join(","), timestamp))
+}
+
 fn get_ram_usage(sys: &mut System) -> f32 {
     sys.refresh_memory();
     sys.used_memory() as f32 / 1024.0 / 1024.0 / 1024.0
 }
 
 fn get_heaviest_process(sys: &mut System) -> String {
-    sys.refresh_all();
+    // Scoped to the process list only: `refresh_all` also re-stamps sysinfo's internal
+    // CPU/memory refresh clocks, which would skew the next `cpu_interval`/`mem_interval`
+    // sample's measurement window whenever `process_interval` doesn't line up with them.
+    sys.refresh_processes();
 
     let mut max_cpu_usage: f32 = 0.0;
     let mut heaviest_process_name: String = String::new();
 
-    for (_pid, proc) in sys.processes() {
+    for proc in sys.processes().values() {
         let cpu_usage: f32 = proc.cpu_usage();
         if cpu_usage > max_cpu_usage {
             max_cpu_usage = cpu_usage;
@@ -191,6 +961,57 @@ fn get_heaviest_process(sys: &mut System) -> String {
     heaviest_process_name
 }
 
+/// Builds one `process_metrics` line-protocol point per process matching `filter`,
+/// tagged by name (and by PID, unless `group_processes` sums same-named processes
+/// together into a single point). Assumes `sys` has already had its process list
+/// refreshed this tick (by `get_heaviest_process`, which it's always called alongside),
+/// since `refresh_processes` is expensive enough that it shouldn't run twice per tick.
+fn get_filtered_process_metrics(
+    sys: &mut System,
+    filter: &ProcessFilter,
+    group_processes: bool,
+    timestamp: u128,
+) -> Vec<String> {
+    if group_processes {
+        let mut grouped: HashMap<String, (f32, u64)> = HashMap::new();
+
+        for proc in sys.processes().values() {
+            let name: &str = proc.name();
+            if !filter.matches(name) {
+                continue;
+            }
+            let totals: &mut (f32, u64) = grouped.entry(name.to_string()).or_insert((0.0, 0));
+            totals.0 += proc.cpu_usage();
+            totals.1 += proc.memory();
+        }
+
+        grouped
+            .into_iter()
+            .map(|(name, (cpu_usage, memory_bytes))| {
+                format!(
+                    "process_metrics,name={} cpu_usage={},memory_bytes={} {}",
+                    escape_tag_value(&name), cpu_usage, memory_bytes, timestamp
+                )
+            })
+            .collect()
+    } else {
+        sys.processes()
+            .values()
+            .filter(|proc| filter.matches(proc.name()))
+            .map(|proc| {
+                format!(
+                    "process_metrics,name={},pid={} cpu_usage={},memory_bytes={} {}",
+                    escape_tag_value(proc.name()),
+                    proc.pid(),
+                    proc.cpu_usage(),
+                    proc.memory(),
+                    timestamp
+                )
+            })
+            .collect()
+    }
+}
+
 fn get_gpu_info(exclude_gpu: bool) -> (f32, f32, f32) {
     if exclude_gpu {
         return (-1.0, -1.0, -1.0);
@@ -215,3 +1036,161 @@ fn get_gpu_info(exclude_gpu: bool) -> (f32, f32, f32) {
         Err(_) => (-1.0, -1.0, -1.0),
     }
 }
+
+/// Builds one `disk_metrics` line-protocol point per mounted filesystem, combining
+/// usage (total/used/available/percent) from sysinfo's `Disks` with read/write
+/// throughput computed as a delta against `disk_traffic`.
+fn get_disk_info(disks: &mut Disks, disk_traffic: &mut DiskTraffic, timestamp: u128) -> Vec<String> {
+    disks.refresh_list();
+    disks.refresh();
+
+    let now: u64 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs();
+
+    let mut lines: Vec<String> = Vec::with_capacity(disks.list().len());
+
+    for disk in disks.list() {
+        let mount_point: String = disk.mount_point().to_string_lossy().to_string();
+        let device: String = block_device_name(&disk.name().to_string_lossy());
+
+        let total_bytes: u64 = disk.total_space();
+        let available_bytes: u64 = disk.available_space();
+        let used_bytes: u64 = total_bytes.saturating_sub(available_bytes);
+        let usage_percent: f64 = if total_bytes > 0 {
+            used_bytes as f64 / total_bytes as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let (read_bytes_per_sec, write_bytes_per_sec) = disk_traffic.throughput_for(&device, now);
+
+        lines.push(format!(
+            "disk_metrics,mount={},device={} total_bytes={},used_bytes={},available_bytes={},usage_percent={},read_bytes_per_sec={},write_bytes_per_sec={} {}",
+            escape_tag_value(&mount_point),
+            escape_tag_value(&device),
+            total_bytes,
+            used_bytes,
+            available_bytes,
+            usage_percent,
+            read_bytes_per_sec,
+            write_bytes_per_sec,
+            timestamp
+        ));
+    }
+
+    disk_traffic.timestamp = now;
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_tag_value_escapes_reserved_characters() {
+        assert_eq!(escape_tag_value("plain"), "plain");
+        assert_eq!(escape_tag_value("a,b"), "a\\,b");
+        assert_eq!(escape_tag_value("a=b"), "a\\=b");
+        assert_eq!(escape_tag_value("a b"), "a\\ b");
+        assert_eq!(escape_tag_value("a\\b"), "a\\\\b");
+        assert_eq!(escape_tag_value("/mnt/My Passport"), "/mnt/My\\ Passport");
+    }
+
+    #[test]
+    fn block_device_name_passes_through_devices_with_no_sysfs_entry_unchanged() {
+        // No /sys/class/block entry exists for this made-up name, so it's neither
+        // canonicalized nor resolved to a parent whole-disk device.
+        assert_eq!(block_device_name("/dev/made-up-test-device"), "made-up-test-device");
+        assert_eq!(block_device_name("made-up-test-device"), "made-up-test-device");
+    }
+
+    #[test]
+    fn process_filter_substring_matches_contains_only() {
+        let filter: ProcessFilter = ProcessFilter::compile("chrome", true).unwrap();
+        assert!(filter.matches("chrome_worker"));
+        assert!(!filter.matches("firefox"));
+    }
+
+    #[test]
+    fn process_filter_regex_matches_pattern() {
+        let filter: ProcessFilter = ProcessFilter::compile("^chrome.*", false).unwrap();
+        assert!(filter.matches("chrome_worker"));
+        assert!(!filter.matches("my_chrome"));
+    }
+
+    #[test]
+    fn parse_proc_net_snmp_pairs_header_and_value_lines_by_protocol() {
+        let contents: &str = "Ip: Forwarding DefaultTTL InReceives\n\
+             Ip: 1 64 12345\n\
+             Udp: InDatagrams NoPorts\n\
+             Udp: 10 2\n";
+
+        let protocols: HashMap<String, HashMap<String, u64>> = parse_proc_net_snmp(contents);
+
+        assert_eq!(protocols["Ip"]["InReceives"], 12345);
+        assert_eq!(protocols["Udp"]["InDatagrams"], 10);
+        assert_eq!(protocols["Udp"]["NoPorts"], 2);
+    }
+
+    #[test]
+    fn parse_proc_net_snmp_ignores_mismatched_header_value_pairs() {
+        // A header line with no matching value line (protocol names disagree) contributes
+        // nothing rather than panicking or pairing with the wrong line.
+        let contents: &str = "Ip: Forwarding\nUdp: InDatagrams\nIp: 1\n";
+
+        let protocols: HashMap<String, HashMap<String, u64>> = parse_proc_net_snmp(contents);
+
+        assert!(protocols.is_empty());
+    }
+
+    #[test]
+    fn stall_guard_reader_chunks_reads_below_the_cap() {
+        let body: Vec<u8> = vec![0u8; INFLUX_STALL_GUARD_MAX_CHUNK_BYTES * 3];
+        let mut guard: StallGuardReader =
+            StallGuardReader::new(body, 1, Duration::from_secs(5));
+        let mut buf: [u8; INFLUX_STALL_GUARD_MAX_CHUNK_BYTES * 3] =
+            [0u8; INFLUX_STALL_GUARD_MAX_CHUNK_BYTES * 3];
+
+        // Even though the whole body would fit in one read of this buffer, the guard
+        // hands back at most one chunk at a time so a multi-chunk body can't finish
+        // streaming out before any measurement window has a chance to elapse.
+        let n: usize = guard.read(&mut buf).unwrap();
+        assert_eq!(n, INFLUX_STALL_GUARD_MAX_CHUNK_BYTES);
+    }
+
+    #[test]
+    fn stall_guard_reader_aborts_when_a_chunk_boundary_stalls() {
+        let body: Vec<u8> = vec![0u8; INFLUX_STALL_GUARD_MAX_CHUNK_BYTES * 3];
+        let mut guard: StallGuardReader =
+            StallGuardReader::new(body, 1_000_000, Duration::from_millis(50));
+        let mut buf: [u8; INFLUX_STALL_GUARD_MAX_CHUNK_BYTES] =
+            [0u8; INFLUX_STALL_GUARD_MAX_CHUNK_BYTES];
+
+        // First chunk starts the clock.
+        let first: usize = guard.read(&mut buf).unwrap();
+        assert_eq!(first, INFLUX_STALL_GUARD_MAX_CHUNK_BYTES);
+
+        // Simulate the server refusing to drain the socket between chunks: reqwest
+        // wouldn't ask for the next chunk until the previous one is handed off, so a
+        // stall here shows up as a real gap between `read()` calls.
+        thread::sleep(Duration::from_millis(100));
+
+        let err: std::io::Error = guard.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn stall_guard_reader_allows_chunks_spaced_within_the_grace_period() {
+        let body: Vec<u8> = vec![0u8; INFLUX_STALL_GUARD_MAX_CHUNK_BYTES * 3];
+        let mut guard: StallGuardReader =
+            StallGuardReader::new(body, 1, Duration::from_secs(5));
+        let mut buf: [u8; INFLUX_STALL_GUARD_MAX_CHUNK_BYTES] =
+            [0u8; INFLUX_STALL_GUARD_MAX_CHUNK_BYTES];
+
+        for _ in 0..3 {
+            assert!(guard.read(&mut buf).is_ok());
+        }
+    }
+}